@@ -1,11 +1,12 @@
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use arc_swap::ArcSwap;
 use futures_util::stream::FuturesUnordered;
 use helix_core::chars::char_is_word;
 use helix_core::syntax::LanguageServerFeature;
+use helix_core::RopeSlice;
 use helix_event::{
     cancelable_future, cancelation, register_hook, send_blocking, CancelRx, CancelTx,
 };
@@ -32,8 +33,29 @@ use crate::ui::{self, CompletionDetails, CompletionItem, Popup};
 
 use super::Handlers;
 pub use resolve::ResolveHandler;
+mod buffer_word;
+mod fuzzy;
+mod path;
 mod resolve;
 
+/// Identifies which completion source produced a [`CompletionItem`].
+///
+/// Previously every item came from a language server, so `provider` was a
+/// bare `LanguageServerId`. Non-LSP sources (buffer-word scanning, path
+/// completion) need a provider value too, so this enum generalizes it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum CompletionProvider {
+    Lsp(LanguageServerId),
+    BufferWord,
+    Path,
+}
+
+impl From<LanguageServerId> for CompletionProvider {
+    fn from(id: LanguageServerId) -> Self {
+        CompletionProvider::Lsp(id)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum TriggerKind {
     Auto,
@@ -61,6 +83,12 @@ pub(super) struct CompletionHandler {
     /// request (by dropping the handle)
     request: Option<CancelTx>,
     config: Arc<ArcSwap<Config>>,
+    /// Fuzzy-match scores used by the reranks in `show_completion`, kept for
+    /// as long as this handler (and thus the editor) is alive; they are
+    /// reused only while the prefix stays the same. Owned here rather than as a
+    /// `static` so its lifetime is tied to the handler that uses it instead
+    /// of the whole process.
+    match_cache: Arc<Mutex<fuzzy::MatchCache>>,
 }
 
 impl CompletionHandler {
@@ -69,6 +97,7 @@ impl CompletionHandler {
             config,
             request: None,
             trigger: None,
+            match_cache: Arc::new(Mutex::new(fuzzy::MatchCache::default())),
         }
     }
 }
@@ -158,19 +187,50 @@ impl helix_event::AsyncHook for CompletionHandler {
         let trigger = self.trigger.take().expect("debounce always has a trigger");
         let (tx, rx) = cancelation();
         self.request = Some(tx);
+        let match_cache = self.match_cache.clone();
         dispatch_blocking(move |editor, compositor| {
-            request_completion(trigger, rx, editor, compositor)
+            request_completion(trigger, rx, editor, compositor, match_cache)
         });
     }
 }
 
+/// The word-like token ending at `pos` in `text`, used both as the
+/// buffer-word prefix and as the needle for fuzzy reranking.
+fn word_prefix(text: RopeSlice, pos: usize) -> String {
+    text.slice(..pos)
+        .chars_at(pos)
+        .reversed()
+        .take_while(|&c| char_is_word(c))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect()
+}
+
+/// The point the completion menu should stay anchored to: the start of the
+/// identifier currently being typed, or `trigger.pos` itself if the trigger
+/// wasn't preceded by any word characters (e.g. a `.` or `::` trigger char).
+/// This is only computed when the menu is first opened; subsequent
+/// `is_incomplete` refreshes reuse the anchor already stored on the
+/// completion popup instead of recomputing it, so the popup doesn't drift as
+/// new pages stream in.
+fn completion_anchor(text: RopeSlice, trigger: &Trigger) -> usize {
+    let word_len = text
+        .chars_at(trigger.pos)
+        .reversed()
+        .take_while(|&c| char_is_word(c))
+        .count();
+    trigger.pos - word_len
+}
+
 fn request_completion(
     mut trigger: Trigger,
     cancel: CancelRx,
     editor: &mut Editor,
     compositor: &mut Compositor,
+    match_cache: Arc<Mutex<fuzzy::MatchCache>>,
 ) {
-    let (view, doc) = current!(editor);
+    let (view, doc) = current_ref!(editor);
 
 
 
@@ -213,8 +273,16 @@ fn request_completion(
         }
     };
 
+    // Type alias for the output shared by every completion source (LSP,
+    // buffer-word, ...) so they can all live in the same `FuturesUnordered`.
+    // The `Option<(LanguageServerId, CompletionDetails)>` is `Some` only for
+    // LSP sources, which are the only ones that can be `is_incomplete`.
+    type CompletionResult =
+        anyhow::Result<Option<(Vec<CompletionItem>, Option<(LanguageServerId, CompletionDetails)>)>>;
+    type CompletionFuture = std::pin::Pin<Box<dyn std::future::Future<Output = CompletionResult> + Send>>;
+
     let mut seen_language_servers = HashSet::new();
-    let mut futures: FuturesUnordered<_> = doc
+    let mut futures: FuturesUnordered<CompletionFuture> = doc
         .language_servers_with_feature(LanguageServerFeature::Completion)
         .filter(|ls| seen_language_servers.insert(ls.id()))
         .filter(|ls| ls_filter(ls.id()))
@@ -255,7 +323,7 @@ fn request_completion(
             };
 
             let completion_response = ls.completion(doc_id, pos, None, context).unwrap();
-            async move {
+            let fut: CompletionFuture = Box::pin(async move {
                 let json = completion_response.await?;
                 let response: Option<lsp::CompletionResponse> = serde_json::from_value(json)?;
                 let response = response
@@ -264,24 +332,78 @@ fn request_completion(
                         lsp::CompletionResponse::List(CompletionList { is_incomplete, items }) => (items, (language_server_id, CompletionDetails {is_incomplete}))
                     })
                     .map(|(items, comp_type)| (
-                        items.into_iter().map(|item| CompletionItem {item, provider: language_server_id, resolved: false}).collect::<Vec<CompletionItem>>(),
-                        comp_type
+                        items.into_iter().map(|item| CompletionItem {item, provider: CompletionProvider::Lsp(language_server_id), resolved: false}).collect::<Vec<CompletionItem>>(),
+                        Some(comp_type),
                     ));
 
                 anyhow::Ok(response)
-            }
+            });
+            fut
         })
         .collect();
 
+    // Buffer-word completion is a synchronous, bounded scan of already-loaded
+    // documents, so it is computed eagerly and handed to the same
+    // `FuturesUnordered` as an already-resolved future: this lets it
+    // interleave with (and get overwritten/deduped against) LSP results
+    // without needing its own tokio task.
+    //
+    // It only runs on the first page: unlike LSP sources, which are only
+    // re-polled for the servers that reported `is_incomplete` (via
+    // `ls_filter` above), it has no notion of "incomplete" of its own, so
+    // re-running it on every refresh cycle would just rescan the same
+    // buffers and hand back duplicates of items `show_completion` already
+    // merged in from the previous page.
+    if completion.is_none() {
+        let prefix = word_prefix(text, cursor);
+        let buffer_words =
+            buffer_word::buffer_word_items(doc, cursor, &prefix, editor.documents.values());
+        if !buffer_words.is_empty() {
+            let fut: CompletionFuture =
+                Box::pin(std::future::ready(Ok(Some((buffer_words, None)))));
+            futures.push(fut);
+        }
+    }
+
+    // If the cursor sits in a path-like token, read the corresponding
+    // directory on the tokio runtime and merge its entries in the same way
+    // as the buffer-word source. This future is owned by `futures`, which in
+    // turn is owned by `future` below, so it is dropped (and the read
+    // abandoned) as soon as `cancel` fires.
+    //
+    // Like buffer-word completion, it only runs on the first page: a
+    // directory listing has no `is_incomplete` concept either, so
+    // re-reading it on every refresh would just re-append the same entries.
+    if completion.is_none() {
+        let base_dir = doc
+            .path()
+            .and_then(|path| path.parent())
+            .map(|parent| parent.to_path_buf())
+            .unwrap_or_default();
+        if let Some(token) = path::detect(trigger_text, &base_dir) {
+            let fut: CompletionFuture = Box::pin(async move {
+                let items = path::completion_items(token).await;
+                anyhow::Ok(if items.is_empty() {
+                    None
+                } else {
+                    Some((items, None))
+                })
+            });
+            futures.push(fut);
+        }
+    }
+
     let future = async move {
         let mut items = Vec::new();
         let mut cmp_is_incomplete: HashMap<LanguageServerId, CompletionDetails> = HashMap::new();
 
         while let Some(response) = futures.next().await {
             match response {
-                Ok(Some((mut lsp_items, lsp_type_pair))) => {
-                    items.append(&mut lsp_items); 
-                    cmp_is_incomplete.insert(lsp_type_pair.0, lsp_type_pair.1);
+                Ok(Some((mut new_items, lsp_type_pair))) => {
+                    items.append(&mut new_items);
+                    if let Some((id, details)) = lsp_type_pair {
+                        cmp_is_incomplete.insert(id, details);
+                    }
                 },
                 Err(err) => {
                     log::debug!("completion request failed: {err:?}");
@@ -289,6 +411,19 @@ fn request_completion(
                 Ok(None) => (),
             };
         }
+
+        // Buffer-word items only supplement LSP results, so drop any whose
+        // label was already produced by a language server.
+        let lsp_labels: HashSet<String> = items
+            .iter()
+            .filter(|item| matches!(item.provider, CompletionProvider::Lsp(_)))
+            .map(|item| item.item.label.clone())
+            .collect();
+        items.retain(|item| {
+            !matches!(item.provider, CompletionProvider::BufferWord)
+                || !lsp_labels.contains(&item.item.label)
+        });
+
         (items, cmp_is_incomplete)
     };
 
@@ -303,7 +438,15 @@ fn request_completion(
             return;
         }
         dispatch(move |editor, compositor| {
-            show_completion(editor, compositor, items, lsp_cmp_details, trigger, savepoint)
+            show_completion(
+                editor,
+                compositor,
+                items,
+                lsp_cmp_details,
+                trigger,
+                savepoint,
+                &match_cache,
+            )
         })
         .await
     });
@@ -316,6 +459,7 @@ fn show_completion(
     lsp_cmp_details: HashMap<LanguageServerId, CompletionDetails>,
     trigger: Trigger,
     savepoint: Arc<SavePoint>,
+    match_cache: &Mutex<fuzzy::MatchCache>,
 ) {
     let (view, doc) = current_ref!(editor);
     // check if the completion request is stale.
@@ -330,36 +474,38 @@ fn show_completion(
     let size = compositor.size();
     let ui = compositor.find::<ui::EditorView>().unwrap();
     
-    // Persist old completions and completion window offset on is_incomplete
+    // Persist old completions and completion window offset on is_incomplete.
+    // The menu is already open, so reuse the anchor it was opened with
+    // rather than recomputing it from the (now further along) cursor.
     let completion_area = match &ui.completion {
         Some(completion) => {
             let offset = completion.trigger_offset();
 
-            println!("offset: {offset}");
-
             let complete_items = completion.complete_items();
 
-            let all_items = complete_items
+            let mut all_items = complete_items
                 .map(|item| item.clone()) // TODO: Workaround
                 .chain(items.into_iter())
                 .collect::<Vec<_>>();
 
-            // TODO: how to align the new completion menu with the old one? I am trying to set the offset but
-            // it is not working
-            let area = ui.set_completion(editor, savepoint, all_items, lsp_cmp_details, offset, size);
-
-
-            // TODO: do we need to rerank? and Would the completion menu change?
-            // if let Some(completion) = &compositor.find::<ui::EditorView>().unwrap().completion {
-            //     completion.rerank
-            // }
-
-            area
-
-
+            // Rerank the merged list against the current prefix so a fresh
+            // `is_incomplete` page doesn't just get tacked onto the end in
+            // whatever order the server returned it.
+            let prefix = word_prefix(doc.text().slice(..), trigger.pos);
+            fuzzy::rerank(&mut all_items, &prefix, &mut match_cache.lock().unwrap());
 
+            ui.set_completion(editor, savepoint, all_items, lsp_cmp_details, offset, size)
         },
-        None => ui.set_completion(editor, savepoint, items, lsp_cmp_details, trigger.pos, size)
+        // The menu isn't open yet: this is the first page, so compute the
+        // anchor fresh from the current word and let `set_completion` store
+        // it as the `trigger_offset` the branch above will keep reusing.
+        None => {
+            let anchor = completion_anchor(doc.text().slice(..), &trigger);
+            let prefix = word_prefix(doc.text().slice(..), trigger.pos);
+            let mut items = items;
+            fuzzy::rerank(&mut items, &prefix, &mut match_cache.lock().unwrap());
+            ui.set_completion(editor, savepoint, items, lsp_cmp_details, anchor, size)
+        }
     };
 
     let signature_help_area = compositor
@@ -405,12 +551,40 @@ pub fn trigger_auto_completion(
         return;
     }
 
+    // The cursor sits right after a path-like token (`src/`, `./`, `../`,
+    // `~/`, or a partial entry inside such a directory): treat it like a
+    // trigger char so the directory listing shows up without waiting for
+    // `completion_trigger_len` word characters to be typed.
+    let base_dir = doc
+        .path()
+        .and_then(|path| path.parent())
+        .map(|parent| parent.to_path_buf())
+        .unwrap_or_default();
+    if path::detect(text, &base_dir).is_some() {
+        send_blocking(
+            tx,
+            CompletionEvent::TriggerChar {
+                cursor,
+                doc: doc.id(),
+                view: view.id,
+            },
+        );
+        return;
+    }
+
+    // `LanguageConfiguration` (in `helix-core`) has no per-language
+    // `completion-trigger-len` / `completion-timeout` overrides, so both
+    // values come from the global editor config; a real per-language
+    // override would need that schema added first, which is outside what
+    // this change touches.
+    let trigger_len = config.completion_trigger_len;
+
     let is_auto_trigger = !trigger_char_only
         && doc
             .text()
             .chars_at(cursor)
             .reversed()
-            .take(config.completion_trigger_len as usize)
+            .take(trigger_len as usize)
             .all(char_is_word);
 
     if is_auto_trigger {
@@ -431,6 +605,14 @@ fn update_completions(cx: &mut commands::Context, c: Option<char>) {
         if let Some(completion) = &mut editor_view.completion {
             completion.update_filter(c);
 
+            // Re-sort what's still on screen against the narrowed prefix:
+            // `update_filter` only drops items that no longer match, it
+            // doesn't reorder the survivors, so without this the list would
+            // keep whatever order it had before the last keystroke.
+            let (view, doc) = current_ref!(cx.editor);
+            let cursor = doc.selection(view.id).primary().cursor(doc.text().slice(..));
+            let prefix = word_prefix(doc.text().slice(..), cursor);
+            completion.rerank(&prefix);
 
             // Handle completions with is_incomplete
             let ids = completion.incomplete_ids();
@@ -0,0 +1,249 @@
+use std::collections::HashSet;
+
+use helix_core::chars::char_is_word;
+use helix_core::RopeSlice;
+use helix_view::Document;
+
+use crate::ui::CompletionItem;
+
+use super::CompletionProvider;
+
+/// Maximum number of buffer-word candidates returned for a single request.
+const MAX_CANDIDATES: usize = 64;
+/// Maximum number of characters scanned in a single document, bounding the
+/// cost of the scan in very large buffers.
+const MAX_SCAN_CHARS: usize = 50_000;
+/// Maximum number of characters scanned across *all* documents combined, so
+/// a session with many open buffers can't multiply `MAX_SCAN_CHARS` by the
+/// number of open documents and stall the handler.
+const MAX_TOTAL_SCAN_CHARS: usize = 200_000;
+
+/// Scan `doc` in a bounded window around `cursor` (and, lazily, every
+/// document in `other_documents`) for words starting with `prefix`,
+/// returning them as [`CompletionItem`]s tagged with
+/// [`CompletionProvider::BufferWord`].
+///
+/// The scan is centered on `cursor` rather than the start of the document:
+/// words the user is actually near are far more likely to be relevant than
+/// whatever happens to sit in the first [`MAX_SCAN_CHARS`] of a large file,
+/// and centering also means the cost of the scan doesn't depend on how far
+/// into the document the cursor is.
+///
+/// Results only supplement LSP completion, not replace it; filtering out
+/// labels the language server already returned happens once, after every
+/// source has been merged (see `request_completion`'s `lsp_labels` check),
+/// rather than here. The per-document scan is bounded by [`MAX_SCAN_CHARS`],
+/// the scan across all documents combined by [`MAX_TOTAL_SCAN_CHARS`], and
+/// the result count by [`MAX_CANDIDATES`], so neither one huge buffer nor
+/// many open buffers can stall the completion handler.
+pub(crate) fn buffer_word_items<'a>(
+    doc: &Document,
+    cursor: usize,
+    prefix: &str,
+    other_documents: impl IntoIterator<Item = &'a Document>,
+) -> Vec<CompletionItem> {
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+
+    let mut seen = HashSet::new();
+    let mut items = Vec::new();
+    let mut remaining_chars = MAX_TOTAL_SCAN_CHARS;
+
+    collect_words_around(
+        doc.text().slice(..),
+        cursor,
+        prefix,
+        &mut seen,
+        &mut items,
+        &mut remaining_chars,
+    );
+
+    for other in other_documents {
+        if items.len() >= MAX_CANDIDATES || remaining_chars == 0 {
+            break;
+        }
+        if other.id() == doc.id() {
+            continue;
+        }
+        // There's no single relevant cursor in a document the user isn't
+        // editing, so just scan from the start, still bounded the same way.
+        collect_words(
+            other.text().slice(..),
+            prefix,
+            &mut seen,
+            &mut items,
+            &mut remaining_chars,
+        );
+    }
+
+    items
+}
+
+/// Scan the `MAX_SCAN_CHARS`-wide window of `text` centered on `cursor`.
+fn collect_words_around(
+    text: RopeSlice,
+    cursor: usize,
+    prefix: &str,
+    seen: &mut HashSet<String>,
+    items: &mut Vec<CompletionItem>,
+    remaining_chars: &mut usize,
+) {
+    let len_chars = text.len_chars();
+    let cursor = cursor.min(len_chars);
+    let start = cursor.saturating_sub(MAX_SCAN_CHARS / 2);
+    let end = len_chars.min(cursor + MAX_SCAN_CHARS / 2);
+    collect_words(text.slice(start..end), prefix, seen, items, remaining_chars);
+}
+
+fn collect_words(
+    text: RopeSlice,
+    prefix: &str,
+    seen: &mut HashSet<String>,
+    items: &mut Vec<CompletionItem>,
+    remaining_chars: &mut usize,
+) {
+    if prefix.is_empty() {
+        return;
+    }
+    let scan_chars = MAX_SCAN_CHARS.min(*remaining_chars);
+    let mut word = String::new();
+    let mut scanned = 0;
+    for ch in text.chars().take(scan_chars) {
+        scanned += 1;
+        if char_is_word(ch) {
+            word.push(ch);
+            continue;
+        }
+        push_candidate(&mut word, prefix, seen, items);
+    }
+    push_candidate(&mut word, prefix, seen, items);
+    *remaining_chars = remaining_chars.saturating_sub(scanned);
+}
+
+fn push_candidate(
+    word: &mut String,
+    prefix: &str,
+    seen: &mut HashSet<String>,
+    items: &mut Vec<CompletionItem>,
+) {
+    if items.len() < MAX_CANDIDATES
+        && word.len() > prefix.len()
+        && word.starts_with(prefix)
+        && seen.insert(word.clone())
+    {
+        items.push(CompletionItem {
+            item: helix_lsp::lsp::CompletionItem {
+                label: word.clone(),
+                ..Default::default()
+            },
+            provider: CompletionProvider::BufferWord,
+            resolved: true,
+        });
+    }
+    word.clear();
+}
+
+#[cfg(test)]
+mod test {
+    use helix_core::Rope;
+
+    use super::*;
+
+    fn words(text: &str, cursor: usize, prefix: &str) -> Vec<String> {
+        let rope = Rope::from_str(text);
+        let mut seen = HashSet::new();
+        let mut items = Vec::new();
+        let mut remaining_chars = MAX_TOTAL_SCAN_CHARS;
+        collect_words_around(
+            rope.slice(..),
+            cursor,
+            prefix,
+            &mut seen,
+            &mut items,
+            &mut remaining_chars,
+        );
+        items.into_iter().map(|item| item.item.label).collect()
+    }
+
+    #[test]
+    fn finds_words_matching_prefix() {
+        assert_eq!(words("foo foobar baz", 0, "foo"), vec!["foobar"]);
+    }
+
+    #[test]
+    fn excludes_word_equal_to_prefix() {
+        // `word.len() > prefix.len()` in `push_candidate`: typing "foo" with
+        // only "foo" itself in the buffer shouldn't suggest completing to
+        // the exact thing already typed.
+        assert_eq!(words("foo", 3, "foo"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn empty_prefix_returns_nothing() {
+        assert_eq!(words("foo foobar", 0, ""), Vec::<String>::new());
+    }
+
+    #[test]
+    fn scan_window_is_centered_on_cursor() {
+        // `foofaraway` is well outside the `MAX_SCAN_CHARS / 2` window
+        // around `cursor` and is skipped, while `foobar` (right next to
+        // `cursor`) is still found, regardless of how far into the document
+        // `cursor` is.
+        let padding = "x ".repeat(MAX_SCAN_CHARS);
+        let text = format!("foofaraway {padding}foobar");
+        let cursor = text.len();
+        let found = words(&text, cursor, "foo");
+        assert_eq!(found, vec!["foobar"]);
+    }
+
+    #[test]
+    fn dedups_candidates() {
+        let rope = Rope::from_str("foobar foobar foobaz");
+        let mut seen = HashSet::new();
+        let mut items = Vec::new();
+        let mut remaining_chars = MAX_TOTAL_SCAN_CHARS;
+        collect_words_around(
+            rope.slice(..),
+            0,
+            "foo",
+            &mut seen,
+            &mut items,
+            &mut remaining_chars,
+        );
+        let labels: Vec<_> = items.into_iter().map(|item| item.item.label).collect();
+        assert_eq!(labels, vec!["foobar", "foobaz"]);
+    }
+
+    #[test]
+    fn total_scan_budget_is_shared_across_documents() {
+        // Two documents that would each be within MAX_SCAN_CHARS on their
+        // own should still be bounded by MAX_TOTAL_SCAN_CHARS combined: once
+        // the budget runs out, later documents contribute nothing further.
+        let padding = "x ".repeat(MAX_TOTAL_SCAN_CHARS);
+        let first = Rope::from_str(&format!("foobar {padding}"));
+        let second = Rope::from_str("foobaz");
+
+        let mut seen = HashSet::new();
+        let mut items = Vec::new();
+        let mut remaining_chars = MAX_TOTAL_SCAN_CHARS;
+        collect_words_around(
+            first.slice(..),
+            0,
+            "foo",
+            &mut seen,
+            &mut items,
+            &mut remaining_chars,
+        );
+        assert_eq!(remaining_chars, 0);
+        collect_words(
+            second.slice(..),
+            "foo",
+            &mut seen,
+            &mut items,
+            &mut remaining_chars,
+        );
+        let labels: Vec<_> = items.into_iter().map(|item| item.item.label).collect();
+        assert_eq!(labels, vec!["foobar"]);
+    }
+}
@@ -0,0 +1,230 @@
+//! Fuzzy re-ranking for merged completion lists.
+//!
+//! When an `is_incomplete` LSP response streams in a new page, `show_completion`
+//! concatenates it onto the items already on screen. Without reranking, the
+//! result is ordered however the servers happened to return it rather than by
+//! relevance to what the user has actually typed. This module scores each
+//! item against the current prefix with a Smith-Waterman-style subsequence
+//! match (bonuses for word-boundary/camelCase/leading-char hits, a penalty
+//! for gaps) and caches the scores. Cached scores are reused only while the
+//! prefix stays the same; a new prefix clears the cache and every item is
+//! scored again.
+
+use std::collections::HashMap;
+
+use crate::ui::CompletionItem;
+
+const BONUS_FIRST_CHAR: i64 = 12;
+const BONUS_BOUNDARY: i64 = 8;
+const BONUS_CAMEL: i64 = 6;
+const PENALTY_GAP: i64 = 2;
+
+/// Score `candidate` as a case-insensitive subsequence match of `needle`.
+/// Returns `None` if `needle` does not occur as a subsequence of
+/// `candidate` at all.
+pub(crate) fn score(needle: &str, candidate: &str) -> Option<i64> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+
+    let needle: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+    let haystack: Vec<char> = candidate.chars().collect();
+
+    let mut total = 0i64;
+    let mut hay_idx = 0usize;
+    let mut last_match: Option<usize> = None;
+
+    for &needle_char in &needle {
+        let idx = loop {
+            if hay_idx >= haystack.len() {
+                return None;
+            }
+            if haystack[hay_idx]
+                .to_lowercase()
+                .eq(std::iter::once(needle_char))
+            {
+                break hay_idx;
+            }
+            hay_idx += 1;
+        };
+
+        total += if idx == 0 {
+            BONUS_FIRST_CHAR
+        } else {
+            let prev = haystack[idx - 1];
+            if !prev.is_alphanumeric() {
+                BONUS_BOUNDARY
+            } else if prev.is_lowercase() && haystack[idx].is_uppercase() {
+                BONUS_CAMEL
+            } else {
+                0
+            }
+        };
+
+        if let Some(last) = last_match {
+            let gap = idx.saturating_sub(last + 1) as i64;
+            total -= gap * PENALTY_GAP;
+        }
+
+        total += 1;
+        last_match = Some(idx);
+        hay_idx = idx + 1;
+    }
+
+    Some(total)
+}
+
+/// The text a [`CompletionItem`] is matched against: its `filter_text` when
+/// the language server supplied one, its `label` otherwise.
+fn match_text(item: &CompletionItem) -> &str {
+    item.item
+        .filter_text
+        .as_deref()
+        .unwrap_or(&item.item.label)
+}
+
+/// Per-item fuzzy scores against a given prefix, keyed by the item's
+/// `match_text` so reranking again with the same prefix doesn't rescore
+/// items whose text was already scored. Reranking with a different prefix
+/// (as happens after each typed character) clears every cached score.
+#[derive(Debug, Default)]
+pub(crate) struct MatchCache {
+    prefix: String,
+    scores: HashMap<String, i64>,
+}
+
+impl MatchCache {
+    /// Score (and cache) every item in `items` against `prefix`, reusing
+    /// cached scores from a previous call with the same prefix.
+    fn scores(&mut self, prefix: &str, items: &[CompletionItem]) {
+        if self.prefix != prefix {
+            self.scores.clear();
+            self.prefix = prefix.to_string();
+        }
+        for item in items {
+            let text = match_text(item);
+            if !self.scores.contains_key(text) {
+                if let Some(s) = score(prefix, text) {
+                    self.scores.insert(text.to_string(), s);
+                }
+            }
+        }
+    }
+
+    fn get(&self, item: &CompletionItem) -> Option<i64> {
+        self.scores.get(match_text(item)).copied()
+    }
+}
+
+/// Sort `items` by fuzzy relevance to `prefix`, using LSP `sort_text` (falling
+/// back to `preselect`) as a tiebreaker, and stable otherwise so items that
+/// tie keep their relative order (important so the currently selected entry
+/// doesn't jump around as new incomplete pages merge in).
+pub(crate) fn rerank(items: &mut Vec<CompletionItem>, prefix: &str, cache: &mut MatchCache) {
+    cache.scores(prefix, items);
+
+    items.sort_by(|a, b| {
+        let score_a = cache.get(a).unwrap_or(i64::MIN);
+        let score_b = cache.get(b).unwrap_or(i64::MIN);
+        score_b
+            .cmp(&score_a)
+            .then_with(|| match (&a.item.sort_text, &b.item.sort_text) {
+                (Some(a), Some(b)) => a.cmp(b),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            })
+            .then_with(|| b.item.preselect.unwrap_or(false).cmp(&a.item.preselect.unwrap_or(false)))
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn empty_needle_matches_everything_with_zero_score() {
+        assert_eq!(score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn non_subsequence_does_not_match() {
+        assert_eq!(score("xyz", "foobar"), None);
+    }
+
+    #[test]
+    fn is_case_insensitive() {
+        assert_eq!(score("FOO", "foobar"), score("foo", "foobar"));
+    }
+
+    #[test]
+    fn first_char_match_scores_higher_than_mid_word() {
+        // "f" at the very start of "foo" earns `BONUS_FIRST_CHAR`, while the
+        // "f" inside "buffer" is a plain match with no boundary before it.
+        let first = score("f", "foo").unwrap();
+        let mid = score("f", "buffer").unwrap();
+        assert!(first > mid, "{first} should be greater than {mid}");
+    }
+
+    #[test]
+    fn boundary_match_scores_higher_than_mid_word() {
+        // The "b" in "foo_bar" sits right after a `_` word boundary; the "b"
+        // in "rubber" doesn't sit after any boundary.
+        let boundary = score("b", "foo_bar").unwrap();
+        let mid = score("b", "rubber").unwrap();
+        assert!(boundary > mid, "{boundary} should be greater than {mid}");
+    }
+
+    #[test]
+    fn camel_case_match_scores_higher_than_mid_word() {
+        // The "B" in "fooBar" sits right after a lower-to-upper case
+        // transition; the "b" in "grabbed" doesn't.
+        let camel = score("b", "fooBar").unwrap();
+        let mid = score("b", "grabbed").unwrap();
+        assert!(camel > mid, "{camel} should be greater than {mid}");
+    }
+
+    #[test]
+    fn contiguous_match_scores_higher_than_match_with_gaps() {
+        // Matching "ab" contiguously in "cabin" should score higher than
+        // matching it with a big gap in "caxxxxxb" (neither match earns a
+        // first-char or boundary bonus, so only the gap differs).
+        let contiguous = score("ab", "cabin").unwrap();
+        let gappy = score("ab", "caxxxxxb").unwrap();
+        assert!(
+            contiguous > gappy,
+            "{contiguous} should be greater than {gappy}"
+        );
+    }
+
+    fn item(label: &str, sort_text: Option<&str>) -> CompletionItem {
+        CompletionItem {
+            item: helix_lsp::lsp::CompletionItem {
+                label: label.to_string(),
+                sort_text: sort_text.map(str::to_string),
+                ..Default::default()
+            },
+            provider: super::super::CompletionProvider::BufferWord,
+            resolved: true,
+        }
+    }
+
+    #[test]
+    fn rerank_orders_by_score_descending() {
+        let mut items = vec![item("barfoo", None), item("foobar", None)];
+        let mut cache = MatchCache::default();
+        rerank(&mut items, "foo", &mut cache);
+        assert_eq!(items[0].item.label, "foobar");
+    }
+
+    #[test]
+    fn rerank_breaks_ties_with_sort_text() {
+        let mut items = vec![
+            item("foo1", Some("b")),
+            item("foo2", Some("a")),
+        ];
+        let mut cache = MatchCache::default();
+        rerank(&mut items, "foo", &mut cache);
+        assert_eq!(items[0].item.label, "foo2");
+    }
+}
@@ -0,0 +1,169 @@
+use std::path::{Path, PathBuf};
+
+use helix_core::RopeSlice;
+use helix_lsp::lsp;
+use helix_stdx::path::expand_tilde;
+
+use crate::ui::CompletionItem;
+
+use super::CompletionProvider;
+
+/// Cap on the number of directory entries turned into completion items, so
+/// a large directory listing can't stall the completion handler.
+const MAX_ENTRIES: usize = 256;
+/// Cap on how many characters the backward token scan in [`detect`] walks
+/// before giving up, so a very long line (e.g. a minified file or a single
+/// huge string literal) with no path-like token can't stall typing.
+const MAX_TOKEN_CHARS: usize = 4096;
+
+/// A path-like token recognized immediately before the cursor: the
+/// directory that should be listed and whatever partial filename the user
+/// has already typed inside it.
+#[derive(Debug, Clone)]
+pub(crate) struct PathToken {
+    dir: PathBuf,
+    typed: String,
+}
+
+/// Detect whether the text immediately before the cursor looks like a
+/// filesystem-path token, i.e. it ends in `/`, `./`, `../`, `~/`, or is a
+/// partial filename inside such a directory (e.g. `src/fo`), and if so
+/// return the directory to read together with the partial filename typed so
+/// far.
+///
+/// `base` is the directory relative paths are resolved against (the current
+/// document's directory).
+pub(crate) fn detect(text: RopeSlice, base: &Path) -> Option<PathToken> {
+    let mut token: String = text
+        .chars_at(text.len_chars())
+        .reversed()
+        .take(MAX_TOKEN_CHARS)
+        .take_while(|&c| !c.is_whitespace() && !matches!(c, '"' | '\'' | '(' | '[' | '{' | '<' | '='))
+        .collect();
+    token = token.chars().rev().collect();
+
+    // Any token containing a separator is path-like: that covers a bare
+    // directory reference (`src/`) the moment the separator is typed, the
+    // explicit relative/home markers (`./`, `../`, `~/`), and typing a
+    // partial filename inside any of those directories (`src/fo`) without
+    // losing "path mode" the token entered as soon as the user typed `/`.
+    if !token.contains('/') {
+        return None;
+    }
+
+    let (dir_part, typed) = match token.rfind('/') {
+        Some(idx) => (&token[..=idx], token[idx + 1..].to_string()),
+        None => (token.as_str(), String::new()),
+    };
+
+    let dir = expand_tilde(Path::new(dir_part));
+    let dir = if dir.is_absolute() {
+        dir.into_owned()
+    } else {
+        base.join(dir)
+    };
+
+    Some(PathToken { dir, typed })
+}
+
+#[cfg(test)]
+mod test {
+    use helix_core::Rope;
+
+    use super::*;
+
+    fn detect_str(text: &str, base: &str) -> Option<PathToken> {
+        let rope = Rope::from_str(text);
+        detect(rope.slice(..), Path::new(base))
+    }
+
+    #[test]
+    fn rejects_text_with_no_separator() {
+        assert!(detect_str("foo", "/base").is_none());
+    }
+
+    #[test]
+    fn bare_trailing_slash_is_a_directory() {
+        let token = detect_str("src/", "/base").unwrap();
+        assert_eq!(token.dir, Path::new("/base/src"));
+        assert_eq!(token.typed, "");
+    }
+
+    #[test]
+    fn partial_filename_inside_directory_keeps_path_mode() {
+        // Regression test: continuing to type past the separator used to
+        // fall out of "path mode" since the token no longer ended in `/`.
+        let token = detect_str("src/fo", "/base").unwrap();
+        assert_eq!(token.dir, Path::new("/base/src"));
+        assert_eq!(token.typed, "fo");
+    }
+
+    #[test]
+    fn dot_slash_is_relative_to_base() {
+        let token = detect_str("./fo", "/base").unwrap();
+        assert_eq!(token.dir, Path::new("/base/."));
+        assert_eq!(token.typed, "fo");
+    }
+
+    #[test]
+    fn tilde_slash_expands_home() {
+        let token = detect_str("~/fo", "/base").unwrap();
+        assert!(token.dir.is_absolute());
+        assert!(!token.dir.starts_with("/base"));
+        assert_eq!(token.typed, "fo");
+    }
+
+    #[test]
+    fn scan_does_not_walk_past_max_token_chars() {
+        // The only separator lies further back than MAX_TOKEN_CHARS, so the
+        // capped scan never reaches it.
+        let long_line = format!("/{}", "x".repeat(MAX_TOKEN_CHARS * 2));
+        assert!(detect_str(&long_line, "/base").is_none());
+    }
+}
+
+/// Read the directory named by `token` and turn matching entries into
+/// [`CompletionItem`]s tagged with [`CompletionProvider::Path`]. Items carry
+/// a file/folder kind for iconography and insert only the bare filename
+/// segment, not the full path.
+pub(crate) async fn completion_items(token: PathToken) -> Vec<CompletionItem> {
+    let mut read_dir = match tokio::fs::read_dir(&token.dir).await {
+        Ok(read_dir) => read_dir,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut items = Vec::new();
+    while let Ok(Some(entry)) = read_dir.next_entry().await {
+        if items.len() >= MAX_ENTRIES {
+            break;
+        }
+
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !token.typed.is_empty() && !name.starts_with(&token.typed) {
+            continue;
+        }
+        if token.typed.is_empty() && name.starts_with('.') {
+            // Hide dotfiles until the user actually starts typing one.
+            continue;
+        }
+
+        let is_dir = entry.file_type().await.map_or(false, |ty| ty.is_dir());
+        let label = if is_dir { format!("{name}/") } else { name };
+
+        items.push(CompletionItem {
+            item: lsp::CompletionItem {
+                label,
+                kind: Some(if is_dir {
+                    lsp::CompletionItemKind::FOLDER
+                } else {
+                    lsp::CompletionItemKind::FILE
+                }),
+                ..Default::default()
+            },
+            provider: CompletionProvider::Path,
+            resolved: true,
+        });
+    }
+
+    items
+}
@@ -0,0 +1,73 @@
+use helix_lsp::lsp;
+use tokio::time::Instant;
+
+use crate::job::dispatch;
+use crate::ui::{self, CompletionItem};
+
+use super::CompletionProvider;
+
+/// `completionItem/resolve` requests for the currently selected LSP
+/// completion entry. Each event sends its request right away; requests are
+/// not debounced, so moving through the menu sends one per selected entry.
+/// Every request carries an increasing `resolve_id`, which is passed to
+/// `replace_if_stale` together with the resolved item.
+#[derive(Debug, Default)]
+pub struct ResolveHandler {
+    resolve_id: usize,
+}
+
+impl helix_event::AsyncHook for ResolveHandler {
+    type Event = CompletionItem;
+
+    fn handle_event(
+        &mut self,
+        item: Self::Event,
+        _old_timeout: Option<Instant>,
+    ) -> Option<Instant> {
+        // Only LSP-backed items have anything a server can resolve.
+        // Buffer-word and path items are already fully populated.
+        let CompletionProvider::Lsp(language_server_id) = item.provider else {
+            return None;
+        };
+        self.resolve_id = self.resolve_id.wrapping_add(1);
+        let resolve_id = self.resolve_id;
+
+        tokio::spawn(resolve_completion_item(
+            language_server_id,
+            item.item,
+            resolve_id,
+        ));
+        None
+    }
+}
+
+async fn resolve_completion_item(
+    language_server_id: helix_lsp::LanguageServerId,
+    item: lsp::CompletionItem,
+    resolve_id: usize,
+) {
+    dispatch(move |editor, _compositor| {
+        let Some(language_server) = editor.language_server_by_id(language_server_id) else {
+            return;
+        };
+        let Some(future) = language_server.resolve_completion_item(item) else {
+            return;
+        };
+        tokio::spawn(async move {
+            match future.await {
+                Ok(resolved_item) => {
+                    dispatch(move |editor, compositor| {
+                        let editor_view = compositor.find::<ui::EditorView>().unwrap();
+                        if let Some(completion) = &mut editor_view.completion {
+                            completion.replace_if_stale(resolve_id, resolved_item);
+                        }
+                        let _ = editor;
+                    })
+                    .await
+                }
+                Err(err) => log::debug!("completionItem/resolve request failed: {err}"),
+            }
+        });
+    })
+    .await;
+}
@@ -0,0 +1,24 @@
+use helix_lsp::lsp;
+
+use crate::handlers::completion::CompletionProvider;
+
+/// A single entry in the completion popup.
+///
+/// `provider` used to be a bare `LanguageServerId`, back when every item came
+/// from a language server. Non-LSP sources (buffer-word scanning, path
+/// completion) need to identify themselves too, so this now holds a
+/// `CompletionProvider`; LSP items keep their id via `CompletionProvider::Lsp`.
+#[derive(Debug, Clone)]
+pub struct CompletionItem {
+    pub item: lsp::CompletionItem,
+    pub provider: CompletionProvider,
+    pub resolved: bool,
+}
+
+/// Per-language-server bookkeeping carried alongside a batch of completion
+/// items, used to track which language servers returned an `is_incomplete`
+/// list that needs to be re-requested as the user keeps typing.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompletionDetails {
+    pub is_incomplete: bool,
+}
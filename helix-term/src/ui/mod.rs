@@ -0,0 +1,3 @@
+mod completion;
+
+pub use completion::{CompletionDetails, CompletionItem};